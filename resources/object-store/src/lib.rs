@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use shuttle_service::{
+    CachePolicy, CustomError, Error, Factory, ObjectStoreReadyInfo, ResourceBuilder, Type,
+};
+
+/// Get a blob/object-storage bucket to persist files across deployments
+///
+/// ```rust,no_run
+/// #[shuttle_runtime::main]
+/// async fn my_service(
+///     #[shuttle_object_store::Bucket] bucket: shuttle_object_store::Client,
+/// ) -> ShuttleSimpleService {
+///     Ok(MyService { bucket })
+/// }
+/// ```
+#[derive(Serialize)]
+pub struct Bucket {
+    config: (),
+}
+
+#[async_trait]
+impl ResourceBuilder<Client> for Bucket {
+    const TYPE: Type = Type::ObjectStore;
+
+    type Config = ();
+
+    type Output = ObjectStoreReadyInfo;
+
+    fn new() -> Self {
+        Self { config: () }
+    }
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn cache_policy(&self) -> CachePolicy {
+        // `Config` is always `()`, so config-equality caching would reuse the first deployment's
+        // credentials forever. Always re-provision so rotated/revoked credentials take effect.
+        CachePolicy::AlwaysRefresh
+    }
+
+    async fn output(self, factory: &mut dyn Factory) -> Result<Self::Output, Error> {
+        factory.get_object_store().await
+    }
+
+    async fn build(build_data: &Self::Output) -> Result<Client, Error> {
+        Ok(Client::new(build_data.clone()))
+    }
+}
+
+/// The credentials needed to authenticate against a provisioned bucket
+#[derive(Clone)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+/// A ready-to-use handle to the bucket provisioned by [Bucket]
+#[derive(Clone)]
+pub struct Client {
+    info: ObjectStoreReadyInfo,
+    http: reqwest::Client,
+}
+
+impl Client {
+    fn new(info: ObjectStoreReadyInfo) -> Self {
+        Self {
+            info,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// The endpoint of the provisioned bucket
+    pub fn endpoint(&self) -> &str {
+        self.info.endpoint()
+    }
+
+    /// The namespace prefix every key written by this service should be stored under
+    pub fn namespace(&self) -> &str {
+        self.info.namespace()
+    }
+
+    /// The credentials to authenticate against [Self::endpoint] with
+    pub fn credentials(&self) -> Credentials {
+        Credentials {
+            access_key_id: self.info.access_key_id().to_string(),
+            secret_access_key: self.info.secret_access_key().to_string(),
+        }
+    }
+
+    /// Upload `body` to `key`, namespaced under this service's bucket prefix
+    pub async fn put(&self, key: &str, body: Vec<u8>) -> Result<(), Error> {
+        let credentials = self.credentials();
+
+        self.http
+            .put(self.object_url(key)?)
+            .basic_auth(credentials.access_key_id, Some(credentials.secret_access_key))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| Error::Custom(CustomError::new(e)))?
+            .error_for_status()
+            .map_err(|e| Error::Custom(CustomError::new(e)))?;
+
+        Ok(())
+    }
+
+    /// Download the object stored at `key`, namespaced under this service's bucket prefix
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        let credentials = self.credentials();
+
+        let bytes = self
+            .http
+            .get(self.object_url(key)?)
+            .basic_auth(credentials.access_key_id, Some(credentials.secret_access_key))
+            .send()
+            .await
+            .map_err(|e| Error::Custom(CustomError::new(e)))?
+            .error_for_status()
+            .map_err(|e| Error::Custom(CustomError::new(e)))?
+            .bytes()
+            .await
+            .map_err(|e| Error::Custom(CustomError::new(e)))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    fn object_url(&self, key: &str) -> Result<reqwest::Url, Error> {
+        let mut url =
+            reqwest::Url::parse(self.endpoint()).map_err(|e| Error::Custom(CustomError::new(e)))?;
+
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|_| Error::Custom(CustomError::msg("object store endpoint is not a base URL")))?;
+            segments.push(self.namespace());
+
+            for segment in key.split('/') {
+                if segment.is_empty() || segment == "." || segment == ".." {
+                    return Err(Error::Custom(CustomError::msg(format!(
+                        "invalid object key {key:?}: path segments must not be empty, '.' or '..'"
+                    ))));
+                }
+                segments.push(segment);
+            }
+        }
+
+        Ok(url)
+    }
+}
@@ -0,0 +1,49 @@
+use thiserror::Error;
+
+/// A wrapper for custom errors coming from a [crate::Service] or [crate::ResourceBuilder] implementation
+#[derive(Debug)]
+pub struct CustomError(anyhow::Error);
+
+impl CustomError {
+    /// Create a new custom error from any source error
+    pub fn new(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self(anyhow::Error::new(error))
+    }
+
+    /// Create a new custom error from a message
+    pub fn msg(msg: impl std::fmt::Display + std::fmt::Debug + Send + Sync + 'static) -> Self {
+        Self(anyhow::Error::msg(msg))
+    }
+}
+
+impl std::fmt::Display for CustomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for CustomError {}
+
+/// Errors that can occur while provisioning resources or running a [crate::Service]
+#[derive(Error, Debug)]
+pub enum Error {
+    /// Failed to load the secrets for this service
+    #[error("failed to get secrets: {0}")]
+    Secret(String),
+
+    /// Failed to get a database connection
+    #[error("failed to get database connection: {0}")]
+    Database(String),
+
+    /// A [crate::Factory] was asked for a resource it does not know how to provision
+    #[error("not implemented: {0}")]
+    NotImplemented(String),
+
+    /// The health or readiness check did not succeed
+    #[error("health check failed: {0}")]
+    HeathCheckFailed(String),
+
+    /// A custom error raised by a [crate::Service] or [crate::ResourceBuilder] implementation
+    #[error(transparent)]
+    Custom(#[from] CustomError),
+}
@@ -1,4 +1,5 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 use std::{collections::BTreeMap, num::NonZeroU32};
 
 use async_trait::async_trait;
@@ -8,7 +9,7 @@ pub use shuttle_common::{
     deployment::{DeploymentMetadata, Environment},
     project::ProjectName,
     resource::Type,
-    DatabaseReadyInfo, DbInput, DbOutput, SecretStore,
+    DatabaseReadyInfo, DbInput, DbOutput, ObjectStoreReadyInfo, SecretStore,
 };
 
 pub mod error;
@@ -25,18 +26,75 @@ pub mod builder;
 #[async_trait]
 pub trait Factory: Send + Sync {
     /// Get a database connection
+    ///
+    /// This provisions (or reuses) the default instance of `db_type` for this service. To provision
+    /// more than one instance of the same type, use [Self::get_named_db_connection] instead.
     async fn get_db_connection(
         &mut self,
         db_type: database::Type,
-    ) -> Result<DatabaseReadyInfo, crate::Error>;
+    ) -> Result<DatabaseReadyInfo, crate::Error> {
+        self.get_named_db_connection(DEFAULT_DB_NAME, db_type).await
+    }
+
+    /// Get a database connection identified by `name`
+    ///
+    /// `name` is a stable per-service identifier used as the provisioning key, so a service that needs
+    /// more than one database of the same [database::Type] (e.g. a read replica alongside a primary) can
+    /// request each as a distinct, separately cached instance.
+    ///
+    /// Defaults to a "not implemented" error so existing [Factory] implementations keep compiling until
+    /// they add real multi-instance support; override this to provision more than the default instance.
+    async fn get_named_db_connection(
+        &mut self,
+        _name: &str,
+        _db_type: database::Type,
+    ) -> Result<DatabaseReadyInfo, crate::Error> {
+        Err(Error::NotImplemented(
+            "this Factory does not support named database connections".to_string(),
+        ))
+    }
 
     /// Get all the secrets for a service
     async fn get_secrets(&mut self) -> Result<BTreeMap<String, String>, crate::Error>;
 
+    /// Get a blob/object-storage bucket for this service
+    ///
+    /// Returns the bucket endpoint, credentials and a namespace prefix so a service can persist
+    /// uploads across deployments without provisioning an external storage account by hand.
+    ///
+    /// Defaults to a "not implemented" error so existing [Factory] implementations keep compiling until
+    /// they add real object-store provisioning; override this to support `shuttle_object_store::Bucket`.
+    async fn get_object_store(&mut self) -> Result<ObjectStoreReadyInfo, crate::Error> {
+        Err(Error::NotImplemented(
+            "this Factory does not support object-store provisioning".to_string(),
+        ))
+    }
+
     /// Get the metadata for this deployment
     fn get_metadata(&self) -> DeploymentMetadata;
 }
 
+/// The provisioning key used by [Factory::get_db_connection] so that callers which don't care about
+/// naming multiple instances of the same [database::Type] keep getting the same instance back.
+pub const DEFAULT_DB_NAME: &str = "default";
+
+/// Controls when a [ResourceBuilder::Output] cached from a previous deployment may be reused instead of
+/// calling [ResourceBuilder::output] again.
+#[derive(Clone, Copy, Debug)]
+pub enum CachePolicy {
+    /// Reuse the cached output as long as [ResourceBuilder::config] is unchanged from the previous deployment.
+    /// This is the default behaviour.
+    ReuseIfConfigUnchanged,
+
+    /// Never reuse a cached output; always call [ResourceBuilder::output] again. Useful for resources that need
+    /// to rotate credentials on every deployment.
+    AlwaysRefresh,
+
+    /// Reuse the cached output as long as [ResourceBuilder::config] is unchanged and the output was produced less
+    /// than the given [Duration] ago. Useful for resources whose handle can go stale over time.
+    ExpireAfter(Duration),
+}
+
 /// Used to get resources of type `T` from factories.
 ///
 /// This is mainly meant for consumption by our code generator and should generally not be called by users.
@@ -69,9 +127,19 @@ pub trait ResourceBuilder<T> {
     ///
     /// If the exact same config was returned by a previous deployement that used this resource, then [Self::output()]
     /// will not be called to get the builder output again. Rather the output state of the previous deployment
-    /// will be passed to [Self::build()].
+    /// will be passed to [Self::build()]. See [Self::cache_policy] to change this behaviour.
     fn config(&self) -> &Self::Config;
 
+    /// Controls whether a cached [Self::Output] from a previous deployment may be reused instead of calling
+    /// [Self::output] again.
+    ///
+    /// Defaults to [CachePolicy::ReuseIfConfigUnchanged], i.e. the behaviour described on [Self::config]. Override
+    /// this to force re-provisioning on every deployment (e.g. to rotate credentials) or to expire a cached output
+    /// after some duration (e.g. a handle that goes stale).
+    fn cache_policy(&self) -> CachePolicy {
+        CachePolicy::ReuseIfConfigUnchanged
+    }
+
     /// Get the config output of this builder
     ///
     /// This method is where the actual resource provisioning should take place and is expected to take the longest. It
@@ -88,6 +156,44 @@ pub enum Idle {
     AlwaysOn,
 }
 
+/// The cap on the exponential backoff interval in the default [Service::health_check] implementation,
+/// so that a large `max_attempts` can't grow `interval` into overflowing a `Duration` multiplication.
+const MAX_HEALTH_CHECK_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Configures the retry/backoff behaviour of the default [Service::health_check] implementation.
+#[derive(Clone, Copy, Debug)]
+pub struct HealthCheckConfig {
+    /// How long to wait after [Service::bind] returns before making the first attempt
+    pub initial_delay: Duration,
+
+    /// How long to wait between attempts, doubling after each failed attempt up to a cap of 60 seconds
+    pub interval: Duration,
+
+    /// The maximum number of attempts to make before giving up
+    pub max_attempts: u32,
+
+    /// How long to wait for a single attempt to respond before treating it as failed
+    pub timeout: Duration,
+}
+
+impl HealthCheckConfig {
+    /// The default retry/backoff configuration, also used as [Service::HEALTH_CHECK]'s default value.
+    pub const fn new() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(0),
+            interval: Duration::from_secs(1),
+            max_attempts: 5,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// The core trait of the Shuttle platform. Every crate deployed to Shuttle needs to implement this trait.
 ///
 /// Use the [shuttle_runtime::main] macro to expose your implementation to the deployment backend.
@@ -95,23 +201,76 @@ pub enum Idle {
 pub trait Service: Send + Clone {
     const IDLE: Idle = Idle::DoIdle(unsafe { NonZeroU32::new_unchecked(30) });
 
+    /// Configures the retry/backoff behaviour of the default [Self::health_check] implementation, and the
+    /// per-attempt timeout used by the default [Self::readiness_check] implementation.
+    /// Override this if the service needs more (or less) time to warm up than the default allows.
+    const HEALTH_CHECK: HealthCheckConfig = HealthCheckConfig::new();
+
     /// This function is run on startup after loading the service.
     ///
     /// The service can bind to the passed [SocketAddr][SocketAddr] if desired.
     async fn bind(self, addr: SocketAddr) -> Result<(), error::Error>;
 
-    /// This is called after startup to check if the service is healthy.
+    /// This is called after startup to check if the service is alive and should be kept running.
     ///
-    /// Default implementation assumes the service is bound to `addr` and responds with 200 OK on '/_shuttle/healthz'.
+    /// Default implementation assumes the service is bound to `addr` and responds with 200 OK on '/_shuttle/healthz',
+    /// retrying with exponential backoff according to [Self::HEALTH_CHECK] so a service that is merely slow to boot
+    /// isn't mistaken for one that failed to start. The deployer uses this to decide whether to restart the service.
     /// Override this if not relevant.
     async fn health_check(self, addr: &SocketAddr) -> Result<(), error::Error> {
-        reqwest::get(reqwest::Url::parse(&format!("http://{addr}/_shuttle/healthz")).unwrap())
+        let config = Self::HEALTH_CHECK;
+        let url = reqwest::Url::parse(&format!("http://{addr}/_shuttle/healthz")).unwrap();
+
+        tokio::time::sleep(config.initial_delay).await;
+
+        let mut interval = config.interval;
+        let mut last_error = String::from("Health check unsuccessful");
+
+        for attempt in 1..=config.max_attempts {
+            match tokio::time::timeout(config.timeout, reqwest::get(url.clone())).await {
+                Ok(Ok(response)) if response.status().is_success() => return Ok(()),
+                Ok(Ok(response)) => {
+                    last_error = format!("Health check returned status {}", response.status())
+                }
+                Ok(Err(e)) => last_error = e.to_string(),
+                Err(_) => last_error = format!("Health check timed out after {:?}", config.timeout),
+            }
+
+            if attempt < config.max_attempts {
+                tokio::time::sleep(interval).await;
+                interval = interval
+                    .checked_mul(2)
+                    .unwrap_or(MAX_HEALTH_CHECK_BACKOFF)
+                    .min(MAX_HEALTH_CHECK_BACKOFF);
+            }
+        }
+
+        Err(Error::HeathCheckFailed(last_error))
+    }
+
+    /// This is called after startup, and on a loop, to check if the service is ready to serve traffic.
+    ///
+    /// Default implementation assumes the service is bound to `addr` and responds with 200 OK on '/_shuttle/readyz',
+    /// using [Self::HEALTH_CHECK]'s `timeout` so a hung endpoint can't block the deployer's polling loop forever.
+    /// The deployer gates routing traffic to this deployment on readiness, which is separate from
+    /// [Self::health_check]: a service can be alive (and not need a restart) while it is still warming up
+    /// (priming a cache, running migrations) and not yet ready to take requests. Override this if not relevant.
+    async fn readiness_check(self, addr: &SocketAddr) -> Result<(), error::Error> {
+        let url = reqwest::Url::parse(&format!("http://{addr}/_shuttle/readyz")).unwrap();
+
+        tokio::time::timeout(Self::HEALTH_CHECK.timeout, reqwest::get(url))
             .await
+            .map_err(|_| {
+                Error::HeathCheckFailed(format!(
+                    "Readiness check timed out after {:?}",
+                    Self::HEALTH_CHECK.timeout
+                ))
+            })?
             .map_err(|e| Error::HeathCheckFailed(e.to_string()))?
             .status()
             .is_success()
             .then(|| ())
-            .ok_or(Error::HeathCheckFailed("Health check unsuccessful".into()))
+            .ok_or(Error::HeathCheckFailed("Readiness check unsuccessful".into()))
     }
 
     /// Called before shutdown of this service happens. Gives time for service to do graceful shutdown.